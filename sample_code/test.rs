@@ -1,10 +1,63 @@
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::Read;
+use std::io::{BufRead, BufReader, Read};
+
+// Builds a SQL query from a template with `?` placeholders and a separate
+// list of bound parameter values, so callers never concatenate untrusted
+// input into the query text.
+struct QueryBuilder {
+    template: String,
+    params: Vec<String>,
+}
+
+impl QueryBuilder {
+    fn new(template: &str) -> Self {
+        QueryBuilder {
+            template: template.to_string(),
+            params: Vec::new(),
+        }
+    }
+
+    fn bind(&mut self, value: impl Into<String>) -> &mut Self {
+        self.params.push(value.into());
+        self
+    }
+
+    // Returns the template and its ordered parameters for a driver to bind
+    // (e.g. via a prepared statement), without ever substituting them into
+    // the query text itself.
+    fn render(&self) -> (String, Vec<String>) {
+        (self.template.clone(), self.params.clone())
+    }
+
+    // Inlines the bound parameters for drivers with no parameter binding
+    // support, quoting and escaping each value and rejecting embedded NULs.
+    fn render_escaped(&self) -> Result<String, Box<dyn std::error::Error>> {
+        for param in &self.params {
+            if param.contains('\0') {
+                return Err(format!("parameter '{}' contains an embedded NUL byte", param).into());
+            }
+        }
+
+        // Walk the template rather than the rendered text, so a '?' inside
+        // an already inlined value is never taken for a placeholder.
+        let mut rendered = String::with_capacity(self.template.len());
+        let mut params = self.params.iter();
+        for ch in self.template.chars() {
+            match if ch == '?' { params.next() } else { None } {
+                Some(param) => rendered.push_str(&format!("'{}'", param.replace('\'', "''"))),
+                None => rendered.push(ch),
+            }
+        }
+
+        Ok(rendered)
+    }
+}
 
 struct UserManager {
     users: Vec<String>,
-    scores: HashMap<String, i32>,
+    scores: HashMap<String, Vec<i32>>,
+    aliases: HashMap<String, String>,
 }
 
 impl UserManager {
@@ -12,6 +65,7 @@ impl UserManager {
         UserManager {
             users: Vec::new(),
             scores: HashMap::new(),
+            aliases: HashMap::new(),
         }
     }
     
@@ -20,15 +74,257 @@ impl UserManager {
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
         
-        let first_line = contents.lines().next().unwrap();
-        
+        let _first_line = contents.lines().next().unwrap();
+
         for line in contents.lines() {
             self.users.push(line.to_string());
         }
         
         Ok(())
     }
-    
+
+    fn chars_from_file(
+        &self,
+        filename: &str,
+    ) -> Result<impl Iterator<Item = Result<char, std::io::Error>>, std::io::Error> {
+        let file = File::open(filename)?;
+        let reader = BufReader::new(file);
+
+        Ok(reader.lines().flat_map(|line| match line {
+            Ok(line) => {
+                let chars: Vec<char> = line.chars().collect();
+                chars.into_iter().map(Ok).collect::<Vec<_>>().into_iter()
+            }
+            Err(e) => vec![Err(e)].into_iter(),
+        }))
+    }
+
+    fn load_scores_csv(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut file = File::open(filename)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        self.ingest_scores_csv(&contents)
+    }
+
+    fn ingest_scores_csv(&mut self, contents: &str) -> Result<(), Box<dyn std::error::Error>> {
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fields = line.split(',').map(str::trim);
+            let username = fields
+                .next()
+                .ok_or_else(|| format!("missing username in line: {}", line))?;
+            let raw_score = fields
+                .next()
+                .ok_or_else(|| format!("missing score in line: {}", line))?;
+            let score: i32 = raw_score
+                .parse()
+                .map_err(|e| format!("invalid score '{}' for user '{}': {}", raw_score, username, e))?;
+
+            self.scores
+                .entry(username.to_string())
+                .or_default()
+                .push(score);
+        }
+
+        Ok(())
+    }
+
+    fn load_users_from_archive(&mut self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let file = File::open(path)?;
+        let reader = BufReader::with_capacity(1024 * 1024, file);
+        let decoder = flate2::read::GzDecoder::new(reader);
+        let mut archive = tar::Archive::new(decoder);
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+
+            let is_csv = entry
+                .path()?
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| ext.eq_ignore_ascii_case("csv"))
+                .unwrap_or(false);
+
+            if !is_csv {
+                continue;
+            }
+
+            let mut contents = String::new();
+            entry.read_to_string(&mut contents)?;
+            self.ingest_scores_csv(&contents)?;
+        }
+
+        Ok(())
+    }
+
+    fn load_from_data(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        const KEYWORDS: &[&str] = &["users", "scores"];
+
+        let mut file = File::open(filename)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        // Parse the whole file into sections first, without touching `self`,
+        // so a later error (unknown keyword, truncated section, bad score)
+        // never leaves the manager partially updated.
+        let mut user_sections: Vec<Vec<String>> = Vec::new();
+        let mut score_sections: Vec<Vec<(String, i32)>> = Vec::new();
+
+        let mut lines = contents.lines().peekable();
+        let mut unknown_keywords = Vec::new();
+
+        while let Some(line) = lines.next() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let keyword = parts.next().ok_or("missing section keyword")?;
+
+            if !KEYWORDS.contains(&keyword) {
+                unknown_keywords.push(keyword.to_string());
+
+                // Consume the bogus section's declared body, same as a known
+                // section, so its lines aren't re-read as section headers of
+                // their own.
+                if let Some(count) = parts.next().and_then(|raw| raw.parse::<usize>().ok()) {
+                    if let Some(next) = lines.peek() {
+                        if next.trim().is_empty() {
+                            lines.next();
+                        }
+                    }
+                    for _ in 0..count {
+                        if lines.next().is_none() {
+                            break;
+                        }
+                    }
+                }
+
+                continue;
+            }
+
+            let count: usize = parts
+                .next()
+                .ok_or_else(|| format!("missing count for section '{}'", keyword))?
+                .parse()
+                .map_err(|e| format!("invalid count for section '{}': {}", keyword, e))?;
+
+            if let Some(next) = lines.peek() {
+                if next.trim().is_empty() {
+                    lines.next();
+                }
+            }
+
+            // Don't pre-allocate on the declared count: it comes straight
+            // from the file and an absurd value would panic with a capacity
+            // overflow before the bounds check below ever runs.
+            let mut collected = Vec::new();
+            for _ in 0..count {
+                let entry = lines.next().ok_or_else(|| {
+                    format!(
+                        "section '{}' declares {} lines but fewer are available",
+                        keyword, count
+                    )
+                })?;
+                collected.push(entry.trim().to_string());
+            }
+
+            match keyword {
+                "users" => user_sections.push(collected),
+                "scores" => {
+                    let mut parsed = Vec::new();
+                    for entry in collected {
+                        let mut fields = entry.split_whitespace();
+                        let name = fields
+                            .next()
+                            .ok_or_else(|| format!("missing name in scores entry: {}", entry))?;
+                        let raw_value = fields
+                            .next()
+                            .ok_or_else(|| format!("missing value in scores entry: {}", entry))?;
+                        let value: i32 = raw_value.parse().map_err(|e| {
+                            format!("invalid score '{}' for user '{}': {}", raw_value, name, e)
+                        })?;
+
+                        parsed.push((name.to_string(), value));
+                    }
+                    score_sections.push(parsed);
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if !unknown_keywords.is_empty() {
+            return Err(format!(
+                "unrecognized section keywords: {}",
+                unknown_keywords.join(", ")
+            )
+            .into());
+        }
+
+        for users in user_sections {
+            self.users.extend(users);
+        }
+        for scores in score_sections {
+            for (name, value) in scores {
+                self.scores.entry(name).or_default().push(value);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load_aliases(&mut self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut file = File::open(path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        for (line_number, line) in contents.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split('\t').filter(|f| !f.is_empty()).collect();
+            if fields.len() != 2 {
+                return Err(format!(
+                    "line {}: expected 'old<TAB><TAB>new', got '{}'",
+                    line_number + 1,
+                    line
+                )
+                .into());
+            }
+
+            self.aliases
+                .insert(fields[0].to_string(), fields[1].to_string());
+        }
+
+        Ok(())
+    }
+
+    fn apply_aliases(&mut self) {
+        for user in self.users.iter_mut() {
+            if let Some(canonical) = self.aliases.get(user) {
+                *user = canonical.clone();
+            }
+        }
+
+        let scores = std::mem::take(&mut self.scores);
+        for (name, values) in scores {
+            let canonical = self.aliases.get(&name).cloned().unwrap_or(name);
+            self.scores.entry(canonical).or_default().extend(values);
+        }
+    }
+
     fn process_user(&self, index: usize) -> String {
         let user = &self.users[index];
         
@@ -44,8 +340,10 @@ impl UserManager {
         String::from_utf8_unchecked(slice.to_vec())
     }
     
-    fn create_query(&self, username: &str) -> String {
-        format!("SELECT * FROM users WHERE name = '{}'", username)
+    fn create_query(&self, username: &str) -> (String, Vec<String>) {
+        let mut query = QueryBuilder::new("SELECT * FROM users WHERE name = ?");
+        query.bind(username.to_string());
+        query.render()
     }
     
     fn recursive_function(&self, n: i32) -> i32 {
@@ -71,14 +369,243 @@ impl UserManager {
 fn main() {
     let mut manager = UserManager::new();
     let _ = manager.load_users("users.txt");
-    
+    let _ = manager.load_scores_csv("scores.csv");
+    let _ = manager.load_from_data("data.txt");
+    let _ = manager.load_users_from_archive("users.tar.gz");
+    let _ = manager.load_aliases("aliases.tsv");
+    manager.apply_aliases();
+
+    if let Ok(chars) = manager.chars_from_file("users.txt") {
+        let char_count = chars.filter_map(Result::ok).count();
+        println!("Character count: {}", char_count);
+    }
+
     let result = manager.process_user(10);
     println!("{}", result);
-    
+
+    for processed in manager.process_all_users() {
+        println!("{}", processed);
+    }
+
     let ptr: *const u8 = std::ptr::null();
     let dangerous_result = unsafe { manager.dangerous_operation(ptr, 100) };
     println!("{}", dangerous_result);
-    
+
+    let (query, params) = manager.create_query("alice");
+    println!("Query: {} (params: {:?})", query, params);
+
+    let mut debug_query = QueryBuilder::new(&query);
+    for param in &params {
+        debug_query.bind(param.clone());
+    }
+    if let Ok(escaped) = debug_query.render_escaped() {
+        println!("Escaped query (debug only, not for execution): {}", escaped);
+    }
+
     let big_result = manager.recursive_function(50000);
     println!("{}", big_result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_temp_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("github_code_test_{}_{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn load_scores_csv_groups_repeated_users() {
+        let path = write_temp_file("scores_ok.csv", "alice, 10\nbob,20\n\nalice,30\n");
+        let mut manager = UserManager::new();
+        manager.load_scores_csv(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(manager.scores.get("alice"), Some(&vec![10, 30]));
+        assert_eq!(manager.scores.get("bob"), Some(&vec![20]));
+    }
+
+    #[test]
+    fn load_scores_csv_rejects_unparseable_score() {
+        let path = write_temp_file("scores_bad.csv", "alice,notanumber\n");
+        let mut manager = UserManager::new();
+        assert!(manager.load_scores_csv(path.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn chars_from_file_yields_characters_across_lines() {
+        let path = write_temp_file("chars.txt", "ab\ncd\n");
+        let manager = UserManager::new();
+        let chars: Result<Vec<char>, _> = manager
+            .chars_from_file(path.to_str().unwrap())
+            .unwrap()
+            .collect();
+        assert_eq!(chars.unwrap(), vec!['a', 'b', 'c', 'd']);
+    }
+
+    #[test]
+    fn chars_from_file_propagates_invalid_utf8_as_err_item() {
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "github_code_test_{}_invalid_utf8.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, [0xFFu8, 0xFEu8, b'\n']).unwrap();
+
+        let manager = UserManager::new();
+        let mut iter = manager.chars_from_file(path.to_str().unwrap()).unwrap();
+        assert!(iter.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn load_from_data_truncated_section_errors_without_mutating() {
+        let path = write_temp_file("data_truncated.txt", "users 3\nalice\nbob\n");
+        let mut manager = UserManager::new();
+
+        assert!(manager.load_from_data(path.to_str().unwrap()).is_err());
+        assert!(manager.users.is_empty());
+    }
+
+    #[test]
+    fn load_from_data_huge_declared_count_errors_without_panicking() {
+        let path = write_temp_file("data_huge.txt", "scores 9000000000000000000\n");
+        let mut manager = UserManager::new();
+
+        assert!(manager.load_from_data(path.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn load_from_data_unknown_keyword_reports_once_and_skips_body() {
+        let path = write_temp_file(
+            "data_unknown.txt",
+            "foo 3\nline1\nline2\nline3\nusers 1\nalice\n",
+        );
+        let mut manager = UserManager::new();
+
+        let err = manager.load_from_data(path.to_str().unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "unrecognized section keywords: foo");
+    }
+
+    #[test]
+    fn load_users_from_archive_skips_non_csv_dir_and_symlink_entries() {
+        use std::io::Write;
+
+        let mut builder = tar::Builder::new(Vec::new());
+
+        let csv_data = b"alice,10\nbob,20\n";
+        let mut csv_header = tar::Header::new_gnu();
+        csv_header.set_size(csv_data.len() as u64);
+        csv_header.set_mode(0o644);
+        builder
+            .append_data(&mut csv_header, "scores.csv", &csv_data[..])
+            .unwrap();
+
+        let notes_data = b"not a csv file";
+        let mut notes_header = tar::Header::new_gnu();
+        notes_header.set_size(notes_data.len() as u64);
+        notes_header.set_mode(0o644);
+        builder
+            .append_data(&mut notes_header, "notes.txt", &notes_data[..])
+            .unwrap();
+
+        let mut dir_header = tar::Header::new_gnu();
+        dir_header.set_entry_type(tar::EntryType::Directory);
+        dir_header.set_size(0);
+        dir_header.set_mode(0o755);
+        builder
+            .append_data(&mut dir_header, "subdir/", &[][..])
+            .unwrap();
+
+        let mut link_header = tar::Header::new_gnu();
+        link_header.set_entry_type(tar::EntryType::Symlink);
+        link_header.set_size(0);
+        link_header.set_mode(0o777);
+        builder
+            .append_link(&mut link_header, "alias.csv", "scores.csv")
+            .unwrap();
+
+        let tar_bytes = builder.into_inner().unwrap();
+
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "github_code_test_{}_archive.tar.gz",
+            std::process::id()
+        ));
+        let file = std::fs::File::create(&path).unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        encoder.write_all(&tar_bytes).unwrap();
+        encoder.finish().unwrap();
+
+        let mut manager = UserManager::new();
+        manager
+            .load_users_from_archive(path.to_str().unwrap())
+            .unwrap();
+
+        assert_eq!(manager.scores.get("alice"), Some(&vec![10]));
+        assert_eq!(manager.scores.get("bob"), Some(&vec![20]));
+        assert!(manager.users.is_empty());
+    }
+
+    #[test]
+    fn load_aliases_rejects_malformed_line_with_line_number() {
+        let path = write_temp_file("aliases_bad.tsv", "old\t\tnew\nbad_line_no_tabs\n");
+        let mut manager = UserManager::new();
+
+        let err = manager.load_aliases(path.to_str().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn apply_aliases_merges_scores_for_colliding_canonical_names() {
+        let mut manager = UserManager::new();
+        manager.users = vec!["alice".to_string(), "alicia".to_string()];
+        manager.scores.insert("alice".to_string(), vec![10]);
+        manager.scores.insert("alicia".to_string(), vec![20]);
+        manager
+            .aliases
+            .insert("alice".to_string(), "alice_canonical".to_string());
+        manager
+            .aliases
+            .insert("alicia".to_string(), "alice_canonical".to_string());
+
+        manager.apply_aliases();
+
+        assert_eq!(manager.users, vec!["alice_canonical", "alice_canonical"]);
+        let mut merged = manager.scores.get("alice_canonical").unwrap().clone();
+        merged.sort();
+        assert_eq!(merged, vec![10, 20]);
+        assert!(!manager.scores.contains_key("alice"));
+        assert!(!manager.scores.contains_key("alicia"));
+    }
+
+    #[test]
+    fn query_builder_render_never_inlines_raw_value() {
+        let malicious = "o'; DROP TABLE users;--";
+        let mut query = QueryBuilder::new("SELECT * FROM users WHERE name = ?");
+        query.bind(malicious.to_string());
+
+        let (template, params) = query.render();
+
+        assert_eq!(template, "SELECT * FROM users WHERE name = ?");
+        assert!(!template.contains(malicious));
+        assert_eq!(params, vec![malicious.to_string()]);
+    }
+
+    #[test]
+    fn query_builder_render_escaped_doubles_quotes_and_rejects_nul() {
+        let mut query = QueryBuilder::new("SELECT * FROM users WHERE name = ?");
+        query.bind("o'; DROP TABLE users;--".to_string());
+
+        let escaped = query.render_escaped().unwrap();
+        assert_eq!(
+            escaped,
+            "SELECT * FROM users WHERE name = 'o''; DROP TABLE users;--'"
+        );
+
+        let mut nul_query = QueryBuilder::new("SELECT * FROM users WHERE name = ?");
+        nul_query.bind("evil\0name".to_string());
+        assert!(nul_query.render_escaped().is_err());
+    }
 }
\ No newline at end of file